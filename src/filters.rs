@@ -0,0 +1,56 @@
+/// Business rules used to decide whether a `Service` title represents an appointment the
+/// user wants to be notified about. Replaces what used to be inline `title_lower.contains(...)`
+/// checks in `check_services` so new vaccines, doses or boosters can be tracked without a
+/// recompile.
+#[derive(Debug, Clone)]
+pub struct ServiceFilters {
+    pub required_terms: Vec<String>,
+    pub exclude_terms: Vec<String>,
+    pub allowed_vaccines: Vec<String>,
+}
+
+impl Default for ServiceFilters {
+    fn default() -> Self {
+        ServiceFilters {
+            required_terms: vec!["impfung".to_string(), "corona".to_string()],
+            exclude_terms: vec!["zweit".to_string()],
+            allowed_vaccines: vec![
+                "biontech".to_string(),
+                "pfizer".to_string(),
+                "moderna".to_string(),
+            ],
+        }
+    }
+}
+
+impl ServiceFilters {
+    /// `title_lower` must already be lowercased, matching the call site in `check_services`.
+    pub fn matches(&self, title_lower: &str) -> bool {
+        if !self
+            .required_terms
+            .iter()
+            .all(|term| title_lower.contains(term.as_str()))
+        {
+            return false;
+        }
+
+        if self
+            .exclude_terms
+            .iter()
+            .any(|term| title_lower.contains(term.as_str()))
+        {
+            return false;
+        }
+
+        if !self.allowed_vaccines.is_empty()
+            && !self
+                .allowed_vaccines
+                .iter()
+                .any(|vaccine| title_lower.contains(vaccine.as_str()))
+        {
+            return false;
+        }
+
+        true
+    }
+}
@@ -13,19 +13,18 @@ use std::sync::Mutex;
 use std::time::Duration;
 use tokio::time::sleep;
 
-struct NotificationConfig {
-    telegram_chat_id: String,
-    telegram_token: String,
-}
-
-impl Debug for NotificationConfig {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("NotificationConfig")
-            .field("telegram_chat_id", &self.telegram_chat_id)
-            .field("telegram_token", &"**************")
-            .finish()
-    }
-}
+mod filters;
+mod notifier;
+mod retry;
+mod store;
+mod toml_config;
+use filters::ServiceFilters;
+use notifier::{build_notifiers, NotificationConfig, Notifier};
+use retry::with_retry;
+use store::SeenSlotStore;
+
+const RETRY_ATTEMPTS: u32 = 4;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
 
 #[derive(Deserialize, Debug)]
 struct DoctorInfoResult {
@@ -58,6 +57,8 @@ struct ModuleItems {
     items: Vec<Module>,
 }
 
+// Only ever logged via `{:?}`, so the fields themselves are never read directly.
+#[allow(dead_code)]
 #[derive(Deserialize, Debug)]
 struct StatusCode {
     code: u64,
@@ -71,11 +72,14 @@ struct Slot {
 
 #[derive(Debug)]
 struct Appointment {
+    // Kept for traceability when inspecting `doctor_map`/the store; not read by this binary.
+    #[allow(dead_code)]
     doc_id: String,
     doc_name: String,
     distance: f64,
     service_id: u64,
     service_title: String,
+    location_label: String,
     dates: Vec<String>,
 }
 
@@ -86,6 +90,7 @@ impl Appointment {
         distance: f64,
         service_id: u64,
         service_title: String,
+        location_label: String,
     ) -> Self {
         Appointment {
             doc_id,
@@ -93,6 +98,7 @@ impl Appointment {
             distance,
             service_id,
             service_title,
+            location_label,
             dates: Vec::new(),
         }
     }
@@ -102,12 +108,41 @@ impl Appointment {
     }
 }
 
-#[derive(Debug)]
-struct Config {
+#[derive(Debug, Clone)]
+struct Location {
     latitude: f64,
     longitude: f64,
     radius: u64,
+    label: String,
+}
+
+struct Config {
+    locations: Vec<Location>,
+    db_path: String,
+    retention_hours: i64,
     notification_config: NotificationConfig,
+    notifiers: Vec<Box<dyn Notifier>>,
+    service_filters: ServiceFilters,
+    poll_interval_day: (u64, u64),
+    poll_interval_night: (u64, u64),
+    night_hours: (u32, u32),
+    per_request_delay_ms: u64,
+}
+
+impl Debug for Config {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Config")
+            .field("locations", &self.locations)
+            .field("db_path", &self.db_path)
+            .field("retention_hours", &self.retention_hours)
+            .field("notification_config", &self.notification_config)
+            .field("service_filters", &self.service_filters)
+            .field("poll_interval_day", &self.poll_interval_day)
+            .field("poll_interval_night", &self.poll_interval_night)
+            .field("night_hours", &self.night_hours)
+            .field("per_request_delay_ms", &self.per_request_delay_ms)
+            .finish()
+    }
 }
 
 type Date = String;
@@ -120,8 +155,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     dotenv::dotenv().ok();
 
-    let config = get_config(&log);
-
     let mut headers = header::HeaderMap::new();
     headers.insert(
         header::USER_AGENT,
@@ -134,71 +167,140 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .build()
         .unwrap();
 
-    send_start_info(&log, &config, &client).await;
+    let config = get_config(&log, client.clone());
+
+    send_start_info(&log, &config).await;
+
+    let store = SeenSlotStore::open(&config.db_path, config.retention_hours)
+        .expect("Failed to open seen-slot store");
+    let mut doctor_map: DoctorMap = store.load_doctor_map().expect("Failed to load seen-slot store");
 
-    let mut doctor_map: DoctorMap = HashMap::new();
     loop {
-        match check_services(&log, &config, &client, &mut doctor_map).await {
-            Ok(option) => match option {
-                None => {
-                    info!(log, "No changes compared to last run");
+        // Doctors are de-duplicated by ref_id across every location searched in this cycle, so
+        // a doctor covered by two overlapping centers isn't queried (and notified) twice.
+        let mut processed_ref_ids: HashSet<String> = HashSet::new();
+        let mut cycle_appointments = Vec::new();
+
+        for location in &config.locations {
+            match check_services(
+                &log,
+                &config,
+                location,
+                &client,
+                &mut doctor_map,
+                &store,
+                &mut processed_ref_ids,
+            )
+            .await
+            {
+                Ok(Some(mut appointments)) => cycle_appointments.append(&mut appointments),
+                Ok(None) => {}
+                Err(e) => {
+                    error!(log, "Error for location {}: {:?}", location.label, e);
                 }
-                Some(appointments) => {
-                    notify(&log, &appointments, &config, &client).await;
+            }
+        }
+
+        if cycle_appointments.is_empty() {
+            info!(log, "No changes compared to last run");
+        } else {
+            notify(&log, &cycle_appointments, &config).await;
+        }
+
+        match store.prune_stale() {
+            Ok(removed) => {
+                for (ref_id, service_id, slot_date) in removed {
+                    if let Some(dates) = doctor_map.get_mut(&ref_id).and_then(|s| s.get_mut(&service_id)) {
+                        dates.remove(&slot_date);
+                    }
                 }
-            },
+            }
             Err(e) => {
-                error!(log, "Error: {:?}", e);
+                error!(log, "Failed to prune stale seen-slots: {:?}", e);
             }
         }
+
         let hour = chrono::Utc::now().hour();
-        let random_sec = if hour >= 22 || hour <= 3 {
-            rand::thread_rng().gen_range(20 * 60..50 * 60)
+        let (start, end) = config.night_hours;
+        let is_night = if start <= end {
+            hour >= start && hour <= end
         } else {
-            rand::thread_rng().gen_range(5 * 60..10 * 60)
+            hour >= start || hour <= end
         };
+        let (min, max) = if is_night {
+            config.poll_interval_night
+        } else {
+            config.poll_interval_day
+        };
+        let random_sec = rand::thread_rng().gen_range(min..max);
         sleep(Duration::from_secs(random_sec)).await;
     }
 }
 
-async fn send_start_info(log: &Logger, config: &Config, client: &Client) {
-    let text = format!(
-        "Starting Version 0.0.4 (Only mRNA) at {}/{}, {}km radius",
-        config.latitude, config.longitude, config.radius
-    );
+async fn send_start_info(log: &Logger, config: &Config) {
+    let locations = config
+        .locations
+        .iter()
+        .map(|location| {
+            format!(
+                "{} ({}/{}, {}km radius)",
+                location.label, location.latitude, location.longitude, location.radius
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+    let vaccines = if config.service_filters.allowed_vaccines.is_empty() {
+        "any vaccine".to_string()
+    } else {
+        config.service_filters.allowed_vaccines.join(", ")
+    };
+    let text = format!("Starting Version 0.0.5 ({}) for {}", vaccines, locations);
     info!(log, "{}", text);
 
-    send_text(client, config, &text, log).await;
+    send_text(config, &text, log).await;
 }
 
 async fn check_services(
     log: &Logger,
     config: &Config,
+    location: &Location,
     client: &Client,
-    notification_map: &mut DoctorMap,
+    doctor_map: &mut DoctorMap,
+    store: &SeenSlotStore,
+    processed_ref_ids: &mut HashSet<String>,
 ) -> Result<Option<Vec<Appointment>>, Box<dyn std::error::Error>> {
     let mut appointments = Vec::new();
-    let mut notification_map_new = HashMap::new();
-
-    let relevant_doctor_info = client
-        .get("https://www.jameda.de/mannheim/corona-impftermine/spezialisten/")
-        .query(&[
-            ("ajaxparams[0]", "add|popular|otb_status"),
-            (
-                "ajaxparams[1]",
-                &format!(
-                    "change|geoball|{}_{}_{}",
-                    config.latitude, config.longitude, config.radius
+
+    let relevant_doctor_info = with_retry(log, RETRY_ATTEMPTS, RETRY_BASE_DELAY, || async {
+        client
+            .get("https://www.jameda.de/mannheim/corona-impftermine/spezialisten/")
+            .query(&[
+                ("ajaxparams[0]", "add|popular|otb_status"),
+                (
+                    "ajaxparams[1]",
+                    &format!(
+                        "change|geoball|{}_{}_{}",
+                        location.latitude, location.longitude, location.radius
+                    ),
                 ),
-            ),
-            ("output", "json"),
-        ])
-        .send()
-        .await?
-        .json::<DoctorInfoResult>()
-        .await?;
+                ("output", "json"),
+            ])
+            .send()
+            .await?
+            .json::<DoctorInfoResult>()
+            .await
+    })
+    .await?;
 
     for doctor_info in relevant_doctor_info.results {
+        if !processed_ref_ids.insert(doctor_info.ref_id.clone()) {
+            debug!(
+                log,
+                "Skipping {}, already covered by another location this cycle", doctor_info.name_kurz
+            );
+            continue;
+        }
+
         debug!(
             log,
             "Checking {}, {}km", doctor_info.name_kurz, doctor_info.entfernung
@@ -218,41 +320,47 @@ async fn check_services(
         }
 
         // Be nice and slow down
-        sleep(Duration::from_millis(2000)).await;
+        sleep(Duration::from_millis(config.per_request_delay_ms)).await;
 
-        let services_for_patients = client
-            .get(format!(
-                "https://booking-service.jameda.de/public/config/modules?refId={}",
-                doctor_info.ref_id
-            ))
-            .send()
-            .await?
-            .json::<ModuleItems>()
-            .await
-            .map(|module_items| {
-                module_items
-                    .items
-                    .iter()
-                    .find(|&module| module.type_ == "knownPatient")
-                    .map(|a| a.services.clone())
-                    .unwrap_or_else(Vec::new)
-            })
-            .unwrap_or_else(|_| Vec::new());
+        let services_for_patients = with_retry(log, RETRY_ATTEMPTS, RETRY_BASE_DELAY, || async {
+            client
+                .get(format!(
+                    "https://booking-service.jameda.de/public/config/modules?refId={}",
+                    doctor_info.ref_id
+                ))
+                .send()
+                .await?
+                .json::<ModuleItems>()
+                .await
+        })
+        .await
+        .map(|module_items| {
+            module_items
+                .items
+                .iter()
+                .find(|&module| module.type_ == "knownPatient")
+                .map(|a| a.services.clone())
+                .unwrap_or_else(Vec::new)
+        })
+        .unwrap_or_else(|_| Vec::new());
 
         debug!(
             log,
             "Services {:?} are reserved for existing patients", services_for_patients
         );
 
-        let services = match client
-            .get(format!(
-                "https://booking-service.jameda.de/public/resources/{}/services",
-                doctor_info.ref_id
-            ))
-            .send()
-            .await?
-            .json::<Vec<Service>>()
-            .await
+        let services = match with_retry(log, RETRY_ATTEMPTS, RETRY_BASE_DELAY, || async {
+            client
+                .get(format!(
+                    "https://booking-service.jameda.de/public/resources/{}/services",
+                    doctor_info.ref_id
+                ))
+                .send()
+                .await?
+                .json::<Vec<Service>>()
+                .await
+        })
+        .await
         {
             Ok(srv) => srv,
             Err(e) => {
@@ -268,16 +376,7 @@ async fn check_services(
 
         for service in services {
             let title_lower = service.title.to_lowercase();
-            if !title_lower.contains("impfung")
-                || !title_lower.contains("corona")
-                || title_lower.contains("zweit")
-            {
-                continue;
-            }
-            if !(title_lower.contains("biontech")
-                || title_lower.contains("pfizer")
-                || title_lower.contains("moderna"))
-            {
+            if !config.service_filters.matches(&title_lower) {
                 continue;
             }
             if services_for_patients.contains(&service.id) {
@@ -296,15 +395,18 @@ async fn check_services(
                 doctor_info.entfernung
             );
 
-            let slots: Vec<Slot> = match client
-                .get(format!(
-                    "https://booking-service.jameda.de/public/resources/{}/slots?serviceId={}",
-                    doctor_info.ref_id, service.id
-                ))
-                .send()
-                .await?
-                .text()
-                .await
+            let slots: Vec<Slot> = match with_retry(log, RETRY_ATTEMPTS, RETRY_BASE_DELAY, || async {
+                client
+                    .get(format!(
+                        "https://booking-service.jameda.de/public/resources/{}/slots?serviceId={}",
+                        doctor_info.ref_id, service.id
+                    ))
+                    .send()
+                    .await?
+                    .text()
+                    .await
+            })
+            .await
             {
                 Ok(response_string) => match serde_json::from_str(&response_string) {
                     Ok(slots) => slots,
@@ -328,7 +430,7 @@ async fn check_services(
             // Format is 2021-05-29T10:15:00+02:00
             let dates = slots
                 .into_iter()
-                .map(|s| s.slot[..s.slot.find('T').unwrap_or_else(|| s.slot.len())].to_owned())
+                .map(|s| s.slot[..s.slot.find('T').unwrap_or(s.slot.len())].to_owned())
                 .collect::<BTreeSet<String>>();
 
             let mut appointment = Appointment::new(
@@ -337,31 +439,26 @@ async fn check_services(
                 doctor_info.entfernung,
                 service.id,
                 service.title,
+                location.label.clone(),
             );
             for date in dates {
                 // Check if the date for the service/appointment id was already reported as available, if not, add it and add to return values
-                let notification_entries = notification_map
+                let is_new = doctor_map
                     .entry(doctor_info.ref_id.clone())
-                    .or_insert_with(HashMap::new)
+                    .or_default()
                     .entry(appointment.service_id)
-                    .or_insert_with(HashSet::new);
-                if notification_entries.insert(date.clone()) {
-                    // Wasn't reported yet nor is it in the new return value, add it
-                    appointment.dates.push(date.clone());
+                    .or_default()
+                    .insert(date.clone());
+
+                // Mirror into the persistent store so a restart doesn't forget what was
+                // already reported; this also keeps the row's last_seen fresh so it isn't pruned.
+                if let Err(e) = store.mark_seen(&doctor_info.ref_id, appointment.service_id, &date) {
+                    error!(log, "Failed to persist seen slot: {:?}", e);
                 }
 
-                // Every entry must be added to the map of the current run. This one will be used for comparision in the next run
-                // (relevant e.g. if old map contained dates that aren't available in the new run, but might be available later again)
-                notification_map_new
-                    .entry(doctor_info.ref_id.clone())
-                    .or_insert_with(HashMap::new)
-                    .entry(appointment.service_id)
-                    .or_insert_with(|| {
-                        let mut set = HashSet::new();
-                        set.insert(date.clone());
-                        set
-                    })
-                    .insert(date.clone());
+                if is_new {
+                    appointment.dates.push(date.clone());
+                }
             }
 
             // Only add Appointments where at least one date is available and not reported yet
@@ -371,10 +468,6 @@ async fn check_services(
         }
     }
 
-    // Set all found entries as old entries, so new ones can be reported
-    info!(log, "NEW: {:?}", notification_map_new);
-    *notification_map = notification_map_new;
-
     if appointments.is_empty() {
         Ok(None)
     } else {
@@ -382,12 +475,13 @@ async fn check_services(
     }
 }
 
-async fn notify(log: &Logger, appointments: &[Appointment], config: &Config, client: &Client) {
+async fn notify(log: &Logger, appointments: &[Appointment], config: &Config) {
     let text = appointments
         .iter()
         .map(|a| {
             format!(
-                "{} ({}, {}km): {}",
+                "[{}] {} ({}, {}km): {}",
+                a.location_label,
                 a.service_title,
                 a.doc_name,
                 a.distance,
@@ -399,27 +493,18 @@ async fn notify(log: &Logger, appointments: &[Appointment], config: &Config, cli
 
     info!(log, "Sending: {}", text);
 
-    send_text(client, config, &text, log).await;
+    send_text(config, &text, log).await;
 }
 
-async fn send_text(client: &Client, config: &Config, text: &str, log: &Logger) {
-    match client
-        .post(format!(
-            "https://api.telegram.org/bot{}/sendMessage",
-            config.notification_config.telegram_token
-        ))
-        .query(&[
-            ("chat_id", &config.notification_config.telegram_chat_id),
-            ("text", &text.to_owned()),
-        ])
-        .send()
-        .await
-    {
-        Ok(resp) => {
-            info!(log, "Sent with status code: {}", resp.status());
-        }
-        Err(e) => {
-            error!(log, "Error during sending: {:?}", e)
+async fn send_text(config: &Config, text: &str, log: &Logger) {
+    for notifier in &config.notifiers {
+        match notifier.send(text).await {
+            Ok(()) => {
+                info!(log, "Sent");
+            }
+            Err(e) => {
+                error!(log, "Error during sending: {:?}", e)
+            }
         }
     }
 }
@@ -437,52 +522,231 @@ fn init_logger() -> Logger {
     slog::Logger::root(drain, o!())
 }
 
-fn get_config(log: &Logger) -> Config {
+/// Parses a `--location` value of the form `lat,long,radius[,label]`, defaulting the label to
+/// the coordinates themselves when none is given.
+fn parse_location(raw: &str) -> Location {
+    let parts: Vec<&str> = raw.split(',').collect();
+    let latitude: f64 = parts
+        .first()
+        .expect("--location is missing a latitude")
+        .trim()
+        .parse()
+        .expect("--location latitude isn't a number");
+    let longitude: f64 = parts
+        .get(1)
+        .expect("--location is missing a longitude")
+        .trim()
+        .parse()
+        .expect("--location longitude isn't a number");
+    let radius: u64 = parts
+        .get(2)
+        .expect("--location is missing a radius")
+        .trim()
+        .parse()
+        .expect("--location radius isn't a number");
+    let label = parts
+        .get(3)
+        .map(|label| label.trim().to_string())
+        .unwrap_or_else(|| format!("{},{}", latitude, longitude));
+
+    Location {
+        latitude,
+        longitude,
+        radius,
+        label,
+    }
+}
+
+fn get_config(log: &Logger, client: Client) -> Config {
     let matches = App::new("Jameda Impfhelper")
         .version("0.0.3")
         .arg(
-            Arg::new("latitude")
-                .long("lat")
-                .value_name("COORDINATE")
-                .about("Sets the latitude of the search start point, e.g. 49.1234567")
-                .required(true),
+            Arg::new("config")
+                .long("config")
+                .value_name("PATH")
+                .about("Sets the path of the TOML config file; any setting it defines overrides the matching CLI/env value")
+                .default_value("config.toml"),
+        )
+        .arg(
+            Arg::new("location")
+                .long("location")
+                .value_name("LAT,LONG,RADIUS[,LABEL]")
+                .about("Adds a search center as lat,long,radius[,label]; repeat to search multiple centers, e.g. --location 49.1234567,8.9876543,100,Mannheim")
+                .multiple_occurrences(true),
+        )
+        .arg(
+            Arg::new("db-path")
+                .long("db-path")
+                .value_name("PATH")
+                .about("Sets the path of the SQLite database used to remember reported slots across restarts")
+                .default_value("state.db"),
+        )
+        .arg(
+            Arg::new("retention-hours")
+                .long("retention-hours")
+                .value_name("HOURS")
+                .about("Hours a slot is remembered after it was last seen, before it is pruned and can be re-reported")
+                .default_value("72"),
         )
         .arg(
-            Arg::new("longitude")
-                .long("long")
-                .value_name("COORDINATE")
-                .about("Sets the longitude of the search start point, e.g. 8.9876543")
-                .required(true),
+            Arg::new("vaccines")
+                .long("vaccines")
+                .value_name("LIST")
+                .about("Comma-separated list of vaccine names to match in the service title, e.g. biontech,moderna")
+                .default_value("biontech,pfizer,moderna"),
         )
         .arg(
-            Arg::new("radius")
-                .long("radius")
-                .value_name("NUMBER")
-                .about("Sets the search radius, e.g. 100")
-                .default_value("100"),
+            Arg::new("required-terms")
+                .long("required-terms")
+                .value_name("LIST")
+                .about("Comma-separated list of terms that must all appear in a service title, e.g. impfung,corona")
+                .default_value("impfung,corona"),
+        )
+        .arg(
+            Arg::new("exclude-terms")
+                .long("exclude-terms")
+                .value_name("LIST")
+                .about("Comma-separated list of terms that must not appear in a service title, e.g. zweit")
+                .default_value("zweit"),
+        )
+        .arg(
+            Arg::new("include-second-dose")
+                .long("include-second-dose")
+                .about("Also match services for a second dose, which are excluded by default")
+                .takes_value(false),
         )
         .get_matches();
 
-    let latitude = matches
-        .value_of_t("latitude")
-        .expect("Latitude isn't a number");
-    let longitude = matches
-        .value_of_t("longitude")
-        .expect("Longitude isn't a number");
-    let radius = matches.value_of_t("radius").expect("Radius isn't a number");
+    let config_path: String = matches
+        .value_of_t("config")
+        .expect("config isn't a valid string");
+    let toml_config = toml_config::load(&config_path);
+
+    let locations: Vec<Location> = toml_config
+        .locations
+        .map(|locations| {
+            locations
+                .into_iter()
+                .map(|location| {
+                    let latitude = location.latitude;
+                    let longitude = location.longitude;
+                    let label = location
+                        .label
+                        .unwrap_or_else(|| format!("{},{}", latitude, longitude));
+                    Location {
+                        latitude,
+                        longitude,
+                        radius: location.radius,
+                        label,
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_else(|| {
+            matches
+                .values_of("location")
+                .expect("At least one --location must be given on the CLI or in config.toml")
+                .map(parse_location)
+                .collect()
+        });
+
+    let db_path = toml_config.db_path.unwrap_or_else(|| {
+        matches
+            .value_of_t("db-path")
+            .expect("db-path isn't a valid string")
+    });
+    let retention_hours = toml_config.retention_hours.unwrap_or_else(|| {
+        matches
+            .value_of_t("retention-hours")
+            .expect("retention-hours isn't a number")
+    });
+
+    let allowed_vaccines: Vec<String> = toml_config
+        .vaccines
+        .map(|vaccines| vaccines.into_iter().map(|vaccine| vaccine.to_lowercase()).collect())
+        .unwrap_or_else(|| {
+            matches
+                .value_of_t::<String>("vaccines")
+                .expect("vaccines isn't a valid string")
+                .split(',')
+                .map(|vaccine| vaccine.trim().to_lowercase())
+                .filter(|vaccine| !vaccine.is_empty())
+                .collect()
+        });
+    let include_second_dose = toml_config
+        .include_second_dose
+        .unwrap_or_else(|| matches.is_present("include-second-dose"));
+
+    let required_terms: Vec<String> = toml_config
+        .required_terms
+        .map(|terms| terms.into_iter().map(|term| term.to_lowercase()).collect())
+        .unwrap_or_else(|| {
+            matches
+                .value_of_t::<String>("required-terms")
+                .expect("required-terms isn't a valid string")
+                .split(',')
+                .map(|term| term.trim().to_lowercase())
+                .filter(|term| !term.is_empty())
+                .collect()
+        });
+    let exclude_terms: Vec<String> = toml_config
+        .exclude_terms
+        .map(|terms| terms.into_iter().map(|term| term.to_lowercase()).collect())
+        .unwrap_or_else(|| {
+            matches
+                .value_of_t::<String>("exclude-terms")
+                .expect("exclude-terms isn't a valid string")
+                .split(',')
+                .map(|term| term.trim().to_lowercase())
+                .filter(|term| !term.is_empty())
+                .collect()
+        });
+
+    let mut service_filters = ServiceFilters {
+        required_terms,
+        exclude_terms,
+        allowed_vaccines,
+    };
+    if include_second_dose {
+        service_filters.exclude_terms.retain(|term| term != "zweit");
+    }
+
+    let poll_interval_day = toml_config.poll_interval_day_secs.unwrap_or((5 * 60, 10 * 60));
+    let poll_interval_night = toml_config
+        .poll_interval_night_secs
+        .unwrap_or((20 * 60, 50 * 60));
+    let night_hours = toml_config.night_hours.unwrap_or((22, 3));
+    let per_request_delay_ms = toml_config.per_request_delay_ms.unwrap_or(2000);
+
+    toml_config::validate_poll_interval("poll_interval_day_secs", poll_interval_day);
+    toml_config::validate_poll_interval("poll_interval_night_secs", poll_interval_night);
+    toml_config::validate_night_hours(night_hours);
 
     let notification_config = NotificationConfig {
-        telegram_chat_id: std::env::var("TELEGRAM_CHAT_ID")
-            .expect("TELEGRAM_CHAT_ID env var must be set"),
-        telegram_token: std::env::var("TELEGRAM_TOKEN")
-            .expect("TELEGRAM_TOKEN env var must be set"),
+        telegram_chat_id: std::env::var("TELEGRAM_CHAT_ID").ok(),
+        telegram_token: std::env::var("TELEGRAM_TOKEN").ok(),
+        smtp_host: std::env::var("SMTP_HOST").ok(),
+        smtp_user: std::env::var("SMTP_USER").ok(),
+        smtp_password: std::env::var("SMTP_PASSWORD").ok(),
+        mail_from: std::env::var("MAIL_FROM").ok(),
+        mail_to: std::env::var("MAIL_TO").ok(),
     };
+    let notifiers = build_notifiers(client, &notification_config);
+    if notifiers.is_empty() {
+        panic!("At least one notifier must be configured: set TELEGRAM_CHAT_ID/TELEGRAM_TOKEN, or SMTP_HOST/SMTP_USER/SMTP_PASSWORD/MAIL_FROM/MAIL_TO");
+    }
 
     let config = Config {
-        latitude,
-        longitude,
-        radius,
+        locations,
+        db_path,
+        retention_hours,
         notification_config,
+        notifiers,
+        service_filters,
+        poll_interval_day,
+        poll_interval_night,
+        night_hours,
+        per_request_delay_ms,
     };
     debug!(log, "Using Config: {:?}", config);
 
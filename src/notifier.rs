@@ -0,0 +1,148 @@
+use async_trait::async_trait;
+use lettre::message::Message;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{SmtpTransport, Transport};
+use reqwest::Client;
+use std::fmt::{Debug, Formatter};
+
+/// Where the raw env-derived notification settings live before they're turned into
+/// concrete `Notifier`s. Kept around separately from the notifiers themselves so `Config`
+/// can still log a redacted summary of what was configured.
+pub struct NotificationConfig {
+    pub telegram_chat_id: Option<String>,
+    pub telegram_token: Option<String>,
+    pub smtp_host: Option<String>,
+    pub smtp_user: Option<String>,
+    pub smtp_password: Option<String>,
+    pub mail_from: Option<String>,
+    pub mail_to: Option<String>,
+}
+
+impl Debug for NotificationConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NotificationConfig")
+            .field("telegram_chat_id", &self.telegram_chat_id)
+            .field(
+                "telegram_token",
+                &self.telegram_token.as_ref().map(|_| "**************"),
+            )
+            .field("smtp_host", &self.smtp_host)
+            .field("smtp_user", &self.smtp_user)
+            .field(
+                "smtp_password",
+                &self.smtp_password.as_ref().map(|_| "**************"),
+            )
+            .field("mail_from", &self.mail_from)
+            .field("mail_to", &self.mail_to)
+            .finish()
+    }
+}
+
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn send(&self, text: &str) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+pub struct TelegramNotifier {
+    client: Client,
+    chat_id: String,
+    token: String,
+}
+
+impl TelegramNotifier {
+    pub fn new(client: Client, chat_id: String, token: String) -> Self {
+        TelegramNotifier {
+            client,
+            chat_id,
+            token,
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    async fn send(&self, text: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let resp = self
+            .client
+            .post(format!(
+                "https://api.telegram.org/bot{}/sendMessage",
+                self.token
+            ))
+            .query(&[("chat_id", &self.chat_id), ("text", &text.to_owned())])
+            .send()
+            .await?;
+        resp.error_for_status()?;
+        Ok(())
+    }
+}
+
+pub struct EmailNotifier {
+    host: String,
+    user: String,
+    password: String,
+    from: String,
+    to: String,
+}
+
+impl EmailNotifier {
+    pub fn new(host: String, user: String, password: String, from: String, to: String) -> Self {
+        EmailNotifier {
+            host,
+            user,
+            password,
+            from,
+            to,
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for EmailNotifier {
+    async fn send(&self, text: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let email = Message::builder()
+            .from(self.from.parse()?)
+            .to(self.to.parse()?)
+            .subject("Jameda Impfhelper")
+            .body(text.to_owned())?;
+
+        let creds = Credentials::new(self.user.clone(), self.password.clone());
+        let mailer = SmtpTransport::relay(&self.host)?.credentials(creds).build();
+
+        // lettre's SmtpTransport is blocking, so ship the send off to a blocking thread
+        // rather than stalling the async runtime.
+        tokio::task::spawn_blocking(move || mailer.send(&email)).await??;
+        Ok(())
+    }
+}
+
+/// Fans the configured settings out into every `Notifier` that has all of its required
+/// fields set, so a user can receive alerts via Telegram, email, or both at once.
+pub fn build_notifiers(client: Client, config: &NotificationConfig) -> Vec<Box<dyn Notifier>> {
+    let mut notifiers: Vec<Box<dyn Notifier>> = Vec::new();
+
+    if let (Some(chat_id), Some(token)) = (&config.telegram_chat_id, &config.telegram_token) {
+        notifiers.push(Box::new(TelegramNotifier::new(
+            client,
+            chat_id.clone(),
+            token.clone(),
+        )));
+    }
+
+    if let (Some(host), Some(user), Some(password), Some(from), Some(to)) = (
+        &config.smtp_host,
+        &config.smtp_user,
+        &config.smtp_password,
+        &config.mail_from,
+        &config.mail_to,
+    ) {
+        notifiers.push(Box::new(EmailNotifier::new(
+            host.clone(),
+            user.clone(),
+            password.clone(),
+            from.clone(),
+            to.clone(),
+        )));
+    }
+
+    notifiers
+}
@@ -0,0 +1,45 @@
+use rand::Rng;
+use slog::{warn, Logger};
+use std::fmt::Debug;
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::sleep;
+
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Retries a fallible async operation with exponential backoff plus jitter, so a single
+/// transient 5xx or network blip doesn't abort an entire scan. Gives up and returns the last
+/// error once `attempts` have been made.
+pub async fn with_retry<T, E, F, Fut>(log: &Logger, attempts: u32, base_delay: Duration, f: F) -> Result<T, E>
+where
+    E: Debug,
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 0;
+    loop {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                attempt += 1;
+                if attempt >= attempts {
+                    return Err(e);
+                }
+
+                let backoff = std::cmp::min(base_delay * 2u32.pow(attempt - 1), MAX_BACKOFF);
+                let jitter = Duration::from_millis(
+                    rand::thread_rng().gen_range(0..=(backoff.as_millis() as u64 / 2).max(1)),
+                );
+                warn!(
+                    log,
+                    "Request failed ({:?}), retrying in {:?} (attempt {}/{})",
+                    e,
+                    backoff + jitter,
+                    attempt,
+                    attempts
+                );
+                sleep(backoff + jitter).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+
+use crate::{Date, DoctorMap};
+
+/// Persists which (doctor, service, date) slots have already been reported, so that a
+/// restart of the binary doesn't re-notify every currently open slot from scratch.
+pub struct SeenSlotStore {
+    conn: Connection,
+    retention: chrono::Duration,
+}
+
+impl SeenSlotStore {
+    pub fn open(path: &str, retention_hours: i64) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS seen_slots (
+                ref_id TEXT NOT NULL,
+                service_id INTEGER NOT NULL,
+                slot_date TEXT NOT NULL,
+                first_seen TEXT NOT NULL,
+                last_seen TEXT NOT NULL,
+                PRIMARY KEY (ref_id, service_id, slot_date)
+            )",
+            [],
+        )?;
+        Ok(SeenSlotStore {
+            conn,
+            retention: chrono::Duration::hours(retention_hours),
+        })
+    }
+
+    /// Loads every persisted row into the nested map the rest of the code works with.
+    pub fn load_doctor_map(&self) -> rusqlite::Result<DoctorMap> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT ref_id, service_id, slot_date FROM seen_slots")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, Date>(2)?,
+            ))
+        })?;
+
+        let mut doctor_map: DoctorMap = HashMap::new();
+        for row in rows {
+            let (ref_id, service_id, slot_date) = row?;
+            let service_id = service_id as u64;
+            doctor_map
+                .entry(ref_id)
+                .or_default()
+                .entry(service_id)
+                .or_default()
+                .insert(slot_date);
+        }
+        Ok(doctor_map)
+    }
+
+    /// Upserts `last_seen` for a currently-available date, returning `true` if the row was
+    /// genuinely new (i.e. an `Appointment` should be emitted for it).
+    pub fn mark_seen(&self, ref_id: &str, service_id: u64, slot_date: &Date) -> rusqlite::Result<bool> {
+        let service_id = service_id as i64;
+        let now = Utc::now().to_rfc3339();
+        let exists = self
+            .conn
+            .query_row(
+                "SELECT 1 FROM seen_slots WHERE ref_id = ?1 AND service_id = ?2 AND slot_date = ?3",
+                params![ref_id, service_id, slot_date],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+
+        if exists {
+            self.conn.execute(
+                "UPDATE seen_slots SET last_seen = ?4 WHERE ref_id = ?1 AND service_id = ?2 AND slot_date = ?3",
+                params![ref_id, service_id, slot_date, now],
+            )?;
+            Ok(false)
+        } else {
+            self.conn.execute(
+                "INSERT INTO seen_slots (ref_id, service_id, slot_date, first_seen, last_seen)
+                 VALUES (?1, ?2, ?3, ?4, ?4)",
+                params![ref_id, service_id, slot_date, now],
+            )?;
+            Ok(true)
+        }
+    }
+
+    /// Deletes rows not seen within the retention window and returns them, so the caller can
+    /// evict the same entries from its in-memory map and re-arm the date if it reappears.
+    pub fn prune_stale(&self) -> rusqlite::Result<Vec<(String, u64, Date)>> {
+        let cutoff = (Utc::now() - self.retention).to_rfc3339();
+        let mut stmt = self
+            .conn
+            .prepare("SELECT ref_id, service_id, slot_date FROM seen_slots WHERE last_seen < ?1")?;
+        let removed = stmt
+            .query_map(params![cutoff], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)? as u64,
+                    row.get::<_, Date>(2)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        drop(stmt);
+        self.conn
+            .execute("DELETE FROM seen_slots WHERE last_seen < ?1", params![cutoff])?;
+        Ok(removed)
+    }
+}
@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+/// Optional overrides loaded from `config.toml`. Any field left out falls back to the
+/// corresponding CLI argument/env var or its built-in default.
+#[derive(Deserialize, Default)]
+pub struct TomlConfig {
+    pub locations: Option<Vec<TomlLocation>>,
+    pub db_path: Option<String>,
+    pub retention_hours: Option<i64>,
+    pub vaccines: Option<Vec<String>>,
+    pub required_terms: Option<Vec<String>>,
+    pub exclude_terms: Option<Vec<String>>,
+    pub include_second_dose: Option<bool>,
+    pub poll_interval_day_secs: Option<(u64, u64)>,
+    pub poll_interval_night_secs: Option<(u64, u64)>,
+    pub night_hours: Option<(u32, u32)>,
+    pub per_request_delay_ms: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct TomlLocation {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub radius: u64,
+    pub label: Option<String>,
+}
+
+/// Loads overrides from `path`, if the file exists. A missing file means "use CLI/env and
+/// built-in defaults for everything", not an error.
+pub fn load(path: &str) -> TomlConfig {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => toml::from_str(&contents).expect("config.toml is malformed"),
+        Err(_) => TomlConfig::default(),
+    }
+}
+
+/// Validates a `(min, max)` poll-interval pair, panicking with a useful message at config-load
+/// time instead of letting `rand::Rng::gen_range` panic deep inside the main loop.
+pub fn validate_poll_interval(name: &str, (min, max): (u64, u64)) {
+    assert!(
+        min < max,
+        "{} must have min < max (got {}..{})",
+        name,
+        min,
+        max
+    );
+}
+
+/// Validates that `night_hours` describes an hour-of-day range.
+pub fn validate_night_hours((start, end): (u32, u32)) {
+    assert!(
+        start <= 23 && end <= 23,
+        "night_hours must be within 0..=23 (got {}..{})",
+        start,
+        end
+    );
+}